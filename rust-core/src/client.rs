@@ -0,0 +1,96 @@
+use crate::{execute_agent_task, AgentResult, AgentTask};
+use std::thread;
+use std::time::Duration;
+
+const RETRY_BACKOFF: Duration = Duration::from_secs(5);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Pulls AgentTasks assigned to this device from a coordinator server,
+// executes them, and reports the results back.
+pub struct ClientHandler {
+    server_url: String,
+    device_uid: String,
+    http: reqwest::blocking::Client,
+}
+
+impl ClientHandler {
+    pub fn new(server_url: &str, device_uid: &str) -> Self {
+        ClientHandler {
+            server_url: server_url.to_string(),
+            device_uid: device_uid.to_string(),
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+
+    // Fetches, executes and reports jobs forever.
+    pub fn run_forever(&self) -> ! {
+        loop {
+            let tasks = self.with_retry("fetch jobs", || self.fetch_jobs());
+
+            for task in tasks {
+                let task_id = task.id.clone();
+                let task_json = match serde_json::to_string(&task) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        eprintln!("[bifrost client] failed to serialize task {}: {}", task_id, e);
+                        continue;
+                    }
+                };
+
+                let result_json = execute_agent_task(&task_json);
+                let result = match serde_json::from_str::<AgentResult>(&result_json) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        eprintln!("[bifrost client] failed to parse result for {}: {}", task_id, e);
+                        continue;
+                    }
+                };
+
+                self.with_retry(&format!("report result {}", task_id), || self.report_result(&result));
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    // Retries `f` after a fixed backoff, logging each failure, until it succeeds.
+    fn with_retry<T, F>(&self, label: &str, mut f: F) -> T
+    where
+        F: FnMut() -> Result<T, String>,
+    {
+        loop {
+            match f() {
+                Ok(value) => return value,
+                Err(e) => {
+                    eprintln!("[bifrost client] {} failed: {} (retrying in {:?})", label, e, RETRY_BACKOFF);
+                    thread::sleep(RETRY_BACKOFF);
+                }
+            }
+        }
+    }
+
+    fn fetch_jobs(&self) -> Result<Vec<AgentTask>, String> {
+        let url = format!("{}/jobs", self.server_url);
+        self.http
+            .get(&url)
+            .query(&[("device_uid", &self.device_uid)])
+            .send()
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?
+            .json::<Vec<AgentTask>>()
+            .map_err(|e| e.to_string())
+    }
+
+    fn report_result(&self, result: &AgentResult) -> Result<(), String> {
+        let url = format!("{}/results", self.server_url);
+        self.http
+            .post(&url)
+            .json(result)
+            .send()
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{execute_agent_task, AgentResult, AgentTask};
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Fixed-size worker pool draining a shared queue, so a burst of submissions
+// from the Android side can't spawn one native thread per task.
+//
+// Limitation: only `SystemAgent` tasks are bounded (see
+// `DEFAULT_SYSTEM_TIMEOUT_MS` below). A `python` or `wasm` task that never
+// returns (e.g. a WASM module with an infinite loop) still wedges its worker
+// forever, and once `WORKER_COUNT` tasks are stuck every later submission
+// queues behind them with no way to observe or recover.
+const WORKER_COUNT: usize = 4;
+
+// Ceiling applied to a `system`/`shell`/`bash` task submitted without its own `timeout_ms`,
+// so a forgotten bound can't wedge a worker permanently.
+const DEFAULT_SYSTEM_TIMEOUT_MS: u64 = 60_000;
+
+// Tracks every task id that has ever been submitted so a retried or
+// duplicated FFI call can never run the same task twice.
+lazy_static::lazy_static! {
+    static ref DEDUP_CACHE: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+    static ref COMPLETED_QUEUE: Arc<Mutex<Vec<AgentResult>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref WORK_QUEUE: Sender<AgentTask> = spawn_worker_pool();
+}
+
+fn spawn_worker_pool() -> Sender<AgentTask> {
+    let (tx, rx) = mpsc::channel::<AgentTask>();
+    let rx = Arc::new(Mutex::new(rx));
+
+    for _ in 0..WORKER_COUNT {
+        let rx = Arc::clone(&rx);
+        thread::spawn(move || loop {
+            let task = rx.lock().unwrap().recv();
+            match task {
+                Ok(task) => run_task(task),
+                Err(_) => break,
+            }
+        });
+    }
+
+    tx
+}
+
+fn run_task(task: AgentTask) {
+    let task_json = match serde_json::to_string(&task) {
+        Ok(json) => json,
+        Err(_) => return,
+    };
+    let result_json = execute_agent_task(&task_json);
+    if let Ok(result) = serde_json::from_str::<AgentResult>(&result_json) {
+        COMPLETED_QUEUE.lock().unwrap().push(result);
+    }
+}
+
+// Queues `task` for a worker to run and returns immediately; returns false
+// without queuing if this task id was already submitted.
+pub fn submit_task(mut task: AgentTask) -> bool {
+    {
+        let mut dedup = DEDUP_CACHE.lock().unwrap();
+        if !dedup.insert(task.id.clone()) {
+            return false;
+        }
+    }
+
+    let runs_on_system_agent = matches!(task.agent_type.as_str(), "system" | "shell" | "bash");
+    if runs_on_system_agent && task.timeout_ms.is_none() {
+        task.timeout_ms = Some(DEFAULT_SYSTEM_TIMEOUT_MS);
+    }
+
+    let _ = WORK_QUEUE.send(task);
+    true
+}
+
+// Drains and returns every result that has finished since the last call.
+pub fn pop_completed() -> Vec<AgentResult> {
+    let mut queue = COMPLETED_QUEUE.lock().unwrap();
+    std::mem::take(&mut *queue)
+}
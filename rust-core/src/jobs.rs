@@ -0,0 +1,222 @@
+use crate::{execute_agent_task, AgentResult, AgentTask};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// A single node in a job plan: a task plus the step ids it must wait on.
+// `{{step_id}}` in the task's command or any environment value is replaced
+// with that predecessor's output before the step runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStep {
+    pub step_id: String,
+    pub task: AgentTask,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+// Constructed directly from the JSON an FFI caller sends across; there's no
+// builder indirection since the only producer is `serde`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobPlan {
+    pub steps: Vec<JobStep>,
+}
+
+// Runs every step in dependency order, interpolating predecessor output
+// into dependents and aborting (not running) any step whose prerequisite
+// failed. Returns a map of step id to its AgentResult.
+pub fn execute_job(plan: &JobPlan) -> HashMap<String, AgentResult> {
+    let mut results: HashMap<String, AgentResult> = HashMap::new();
+    let step_by_id: HashMap<&str, &JobStep> =
+        plan.steps.iter().map(|step| (step.step_id.as_str(), step)).collect();
+
+    let order = match topological_order(&plan.steps) {
+        Ok(order) => order,
+        Err(plan_error) => {
+            let message = plan_error.to_string();
+            for step in &plan.steps {
+                results.insert(step.step_id.clone(), failure(&step.task, &message));
+            }
+            return results;
+        }
+    };
+
+    let mut failed: HashSet<String> = HashSet::new();
+
+    for step_id in order {
+        let step = step_by_id[step_id.as_str()];
+
+        if step.depends_on.iter().any(|dep| failed.contains(dep)) {
+            failed.insert(step.step_id.clone());
+            results.insert(step.step_id.clone(), failure(&step.task, "skipped: a prerequisite step failed"));
+            continue;
+        }
+
+        let task = interpolate_task(step, &results);
+        let result = match serde_json::to_string(&task) {
+            Ok(task_json) => serde_json::from_str::<AgentResult>(&execute_agent_task(&task_json))
+                .unwrap_or_else(|e| failure(&task, &format!("failed to parse step result: {}", e))),
+            Err(e) => failure(&task, &format!("failed to serialize step: {}", e)),
+        };
+
+        if !result.success {
+            failed.insert(step.step_id.clone());
+        }
+        results.insert(step.step_id.clone(), result);
+    }
+
+    results
+}
+
+fn failure(task: &AgentTask, message: &str) -> AgentResult {
+    AgentResult {
+        task_id: task.id.clone(),
+        success: false,
+        output: String::new(),
+        error: Some(message.to_string()),
+        execution_time_ms: 0,
+        exit_code: None,
+    }
+}
+
+fn interpolate_task(step: &JobStep, results: &HashMap<String, AgentResult>) -> AgentTask {
+    let mut task = step.task.clone();
+    for dep in &step.depends_on {
+        if let Some(result) = results.get(dep) {
+            let placeholder = format!("{{{{{}}}}}", dep);
+            task.command = task.command.replace(&placeholder, &result.output);
+            for value in task.environment.values_mut() {
+                *value = value.replace(&placeholder, &result.output);
+            }
+        }
+    }
+    task
+}
+
+enum PlanError {
+    DanglingDependency { step_id: String, missing: String },
+    Cycle { step_id: String },
+}
+
+impl std::fmt::Display for PlanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanError::DanglingDependency { step_id, missing } => {
+                write!(f, "step `{}` depends on unknown step `{}`", step_id, missing)
+            }
+            PlanError::Cycle { step_id } => {
+                write!(f, "job plan has a dependency cycle involving `{}`", step_id)
+            }
+        }
+    }
+}
+
+// Kahn's algorithm; returns the offending step as Err if a dependency is
+// missing from the plan or the plan has a cycle.
+fn topological_order(steps: &[JobStep]) -> Result<Vec<String>, PlanError> {
+    let ids: HashSet<&str> = steps.iter().map(|step| step.step_id.as_str()).collect();
+    for step in steps {
+        for dep in &step.depends_on {
+            if !ids.contains(dep.as_str()) {
+                return Err(PlanError::DanglingDependency {
+                    step_id: step.step_id.clone(),
+                    missing: dep.clone(),
+                });
+            }
+        }
+    }
+
+    let mut in_degree: HashMap<&str, usize> = HashMap::new();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for step in steps {
+        in_degree.entry(step.step_id.as_str()).or_insert(0);
+        for dep in &step.depends_on {
+            *in_degree.entry(step.step_id.as_str()).or_insert(0) += 1;
+            dependents.entry(dep.as_str()).or_default().push(step.step_id.as_str());
+        }
+    }
+
+    let mut queue: VecDeque<&str> =
+        in_degree.iter().filter(|(_, &deg)| deg == 0).map(|(&id, _)| id).collect();
+    let mut order = Vec::new();
+
+    while let Some(step_id) = queue.pop_front() {
+        order.push(step_id.to_string());
+        if let Some(deps) = dependents.get(step_id) {
+            for &dependent in deps {
+                let deg = in_degree.get_mut(dependent).unwrap();
+                *deg -= 1;
+                if *deg == 0 {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+    }
+
+    if order.len() != steps.len() {
+        let stuck = steps
+            .iter()
+            .map(|s| s.step_id.as_str())
+            .find(|id| !order.iter().any(|done| done == id))
+            .unwrap_or("unknown");
+        return Err(PlanError::Cycle { step_id: stuck.to_string() });
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str) -> AgentTask {
+        AgentTask {
+            id: id.to_string(),
+            agent_type: "system".to_string(),
+            command: "true".to_string(),
+            args: vec![],
+            environment: HashMap::new(),
+            timeout_ms: None,
+            strip_ansi: false,
+        }
+    }
+
+    #[test]
+    fn topological_order_runs_dependents_after_prerequisites() {
+        let steps = vec![
+            JobStep { step_id: "b".to_string(), task: task("b"), depends_on: vec!["a".to_string()] },
+            JobStep { step_id: "a".to_string(), task: task("a"), depends_on: vec![] },
+        ];
+
+        let order = topological_order(&steps).expect("plan should not error");
+        assert_eq!(order, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn topological_order_detects_cycle() {
+        let steps = vec![
+            JobStep { step_id: "a".to_string(), task: task("a"), depends_on: vec!["b".to_string()] },
+            JobStep { step_id: "b".to_string(), task: task("b"), depends_on: vec!["a".to_string()] },
+        ];
+
+        let err = topological_order(&steps).unwrap_err();
+        assert!(matches!(err, PlanError::Cycle { .. }));
+    }
+
+    #[test]
+    fn topological_order_detects_dangling_dependency() {
+        let steps = vec![JobStep {
+            step_id: "a".to_string(),
+            task: task("a"),
+            depends_on: vec!["missing".to_string()],
+        }];
+
+        let err = topological_order(&steps).unwrap_err();
+        match err {
+            PlanError::DanglingDependency { step_id, missing } => {
+                assert_eq!(step_id, "a");
+                assert_eq!(missing, "missing");
+            }
+            PlanError::Cycle { .. } => panic!("expected a dangling dependency error, not a cycle"),
+        }
+    }
+}
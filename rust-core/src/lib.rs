@@ -5,9 +5,16 @@ use libc::c_char;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use wasmtime::*;
+use wasmtime_wasi::sync::WasiCtxBuilder;
+use wasmtime_wasi::sync::pipe::WritePipe;
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+mod client;
+mod executor;
+mod jobs;
+mod platform;
+
 // Agent system types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentTask {
@@ -16,6 +23,13 @@ pub struct AgentTask {
     pub command: String,
     pub args: Vec<String>,
     pub environment: HashMap<String, String>,
+    // Only honored by SystemAgent; kills the process past this many ms.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    // Only honored by SystemAgent; strips ANSI escape sequences from its
+    // captured stdout/stderr.
+    #[serde(default)]
+    pub strip_ansi: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,14 +39,90 @@ pub struct AgentResult {
     pub output: String,
     pub error: Option<String>,
     pub execution_time_ms: u64,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+}
+
+// Aggregate of a batch of AgentResults with roll-up counters, so a single
+// FFI crossing can report a whole batch's progress.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CombinedResult {
+    pub results: Vec<AgentResult>,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub total_time_ms: u64,
+    pub success: bool,
+}
+
+impl From<Vec<AgentResult>> for CombinedResult {
+    fn from(results: Vec<AgentResult>) -> Self {
+        let total = results.len();
+        let succeeded = results.iter().filter(|r| r.success).count();
+        let failed = total - succeeded;
+        let total_time_ms = results.iter().map(|r| r.execution_time_ms).sum();
+        let success = total > 0 && failed == 0;
+
+        CombinedResult { results, total, succeeded, failed, total_time_ms, success }
+    }
 }
 
-// Global agent registry
+impl CombinedResult {
+    // Folds another batch's results into this one, recomputing the roll-up
+    // fields so batches can be accumulated incrementally.
+    pub fn merge(mut self, other: CombinedResult) -> Self {
+        self.results.extend(other.results);
+        CombinedResult::from(self.results)
+    }
+}
+
+// Global agent registry. Agents are held behind `Arc` rather than `Box` so a
+// lookup can clone the handle and release the registry lock before calling
+// `execute` — needed so an agent's own `execute` (e.g. the platform agent)
+// can read the registry without deadlocking against itself.
 lazy_static::lazy_static! {
-    static ref AGENT_REGISTRY: Arc<Mutex<HashMap<String, Box<dyn Agent + Send + Sync>>>> = 
+    static ref AGENT_REGISTRY: Arc<Mutex<HashMap<String, Arc<dyn Agent + Send + Sync>>>> =
         Arc::new(Mutex::new(HashMap::new()));
 }
 
+// Where a task currently sits in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgentState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatus {
+    pub state: AgentState,
+    pub updated_at_ms: u64,
+}
+
+// Global task registry, keyed by AgentTask::id, so the Android layer can
+// query lifecycle state for a task without waiting on its result.
+lazy_static::lazy_static! {
+    static ref TASK_REGISTRY: Arc<Mutex<HashMap<String, TaskStatus>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn set_task_state(task_id: &str, state: AgentState) {
+    let mut registry = TASK_REGISTRY.lock().unwrap();
+    registry.insert(
+        task_id.to_string(),
+        TaskStatus { state, updated_at_ms: now_ms() },
+    );
+}
+
 // Agent trait for different execution engines
 pub trait Agent: Send + Sync {
     fn execute(&self, task: &AgentTask) -> AgentResult;
@@ -79,6 +169,7 @@ impl Agent for PythonAgent {
                         output,
                         error: None,
                         execution_time_ms: start_time.elapsed().as_millis() as u64,
+                        exit_code: None,
                     }
                 }
                 Err(e) => AgentResult {
@@ -87,6 +178,7 @@ impl Agent for PythonAgent {
                     output: String::new(),
                     error: Some(format!("Python error: {}", e)),
                     execution_time_ms: start_time.elapsed().as_millis() as u64,
+                    exit_code: None,
                 }
             }
         });
@@ -112,21 +204,105 @@ impl WasmAgent {
     }
 }
 
+impl WasmAgent {
+    // `command` is either a filesystem path to a .wasm/.wat module or a
+    // base64:-prefixed blob of the module contents.
+    fn load_module_bytes(command: &str) -> Result<Vec<u8>, String> {
+        if let Some(encoded) = command.strip_prefix("base64:") {
+            base64::decode(encoded).map_err(|e| format!("invalid base64 module: {}", e))
+        } else {
+            std::fs::read(command).map_err(|e| format!("failed to read module {}: {}", command, e))
+        }
+    }
+
+    // Compiles and runs the module, capturing stdout/stderr via an
+    // in-memory WASI pipe.
+    fn run_module(&self, task: &AgentTask) -> Result<AgentResult, String> {
+        let bytes = Self::load_module_bytes(&task.command)?;
+        let module = Module::new(&self.engine, &bytes)
+            .map_err(|e| format!("failed to compile WASM module: {}", e))?;
+
+        let stdout = WritePipe::new_in_memory();
+        let stderr = WritePipe::new_in_memory();
+
+        let mut wasi_builder = WasiCtxBuilder::new();
+        for (key, value) in &task.environment {
+            wasi_builder.env(key, value)
+                .map_err(|e| format!("invalid WASI env var {}: {}", key, e))?;
+        }
+        let wasi = wasi_builder
+            .stdout(Box::new(stdout.clone()))
+            .stderr(Box::new(stderr.clone()))
+            .build();
+
+        let mut store = Store::new(&self.engine, wasi);
+        let mut linker: Linker<wasmtime_wasi::WasiCtx> = Linker::new(&self.engine);
+        wasmtime_wasi::sync::add_to_linker(&mut linker, |ctx| ctx)
+            .map_err(|e| format!("failed to wire WASI imports: {}", e))?;
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(|e| format!("failed to instantiate module: {}", e))?;
+
+        // Default entry point is `_start` (the WASI convention); callers can
+        // name a different exported function as the first arg.
+        let entry_name = task.args.get(0).cloned().unwrap_or_else(|| "_start".to_string());
+        let entry = instance
+            .get_typed_func::<(), ()>(&mut store, &entry_name)
+            .map_err(|e| format!("export `{}` not found: {}", entry_name, e))?;
+
+        let call_result = entry.call(&mut store, ());
+        drop(store);
+
+        let output = Self::drain_pipe(stdout);
+        let captured_stderr = Self::drain_pipe(stderr);
+
+        match call_result {
+            Ok(()) => Ok(AgentResult {
+                task_id: task.id.clone(),
+                success: true,
+                output,
+                error: if captured_stderr.is_empty() { None } else { Some(captured_stderr) },
+                execution_time_ms: 0,
+                exit_code: None,
+            }),
+            Err(trap) => Ok(AgentResult {
+                task_id: task.id.clone(),
+                success: false,
+                output,
+                error: Some(format!("WASM trap: {}", trap)),
+                execution_time_ms: 0,
+                exit_code: None,
+            }),
+        }
+    }
+
+    fn drain_pipe(pipe: WritePipe<std::io::Cursor<Vec<u8>>>) -> String {
+        pipe.try_into_inner()
+            .map(|cursor| String::from_utf8_lossy(&cursor.into_inner()).into_owned())
+            .unwrap_or_default()
+    }
+}
+
 impl Agent for WasmAgent {
     fn execute(&self, task: &AgentTask) -> AgentResult {
         let start_time = std::time::Instant::now();
-        
-        // For now, just acknowledge WASM execution
-        // In a full implementation, this would compile and run WASM modules
-        AgentResult {
-            task_id: task.id.clone(),
-            success: true,
-            output: format!("WASM agent executed: {}", task.command),
-            error: None,
-            execution_time_ms: start_time.elapsed().as_millis() as u64,
-        }
+
+        let mut result = match self.run_module(task) {
+            Ok(result) => result,
+            Err(e) => AgentResult {
+                task_id: task.id.clone(),
+                success: false,
+                output: String::new(),
+                error: Some(e),
+                execution_time_ms: 0,
+                exit_code: None,
+            },
+        };
+        result.execution_time_ms = start_time.elapsed().as_millis() as u64;
+        result
     }
-    
+
     fn supports(&self, agent_type: &str) -> bool {
         agent_type == "wasm"
     }
@@ -135,38 +311,179 @@ impl Agent for WasmAgent {
 // System command agent for shell/system commands
 pub struct SystemAgent;
 
+// What happened while waiting for a spawned child to finish.
+enum WaitOutcome {
+    Exited(std::process::ExitStatus),
+    TimedOut,
+    Error(String),
+}
+
+// Puts the child in its own process group so a timeout can kill the whole
+// group, not just the immediate `sh` pid; otherwise a backgrounded or
+// forked descendant (e.g. `sleep 9999 &`) keeps the stdout/stderr pipes
+// open after `sh` is reaped and the read threads below never see EOF.
+fn spawn_in_new_process_group(command: &mut std::process::Command) -> std::io::Result<std::process::Child> {
+    use std::os::unix::process::CommandExt;
+    unsafe {
+        command.pre_exec(|| {
+            libc::setpgid(0, 0);
+            Ok(())
+        });
+    }
+    command.spawn()
+}
+
+fn kill_process_group(pid: u32) {
+    unsafe {
+        libc::killpg(pid as i32, libc::SIGKILL);
+    }
+}
+
+// Polls the child rather than blocking on `wait()` so a `timeout_ms` can be
+// enforced; kills the child's whole process group and reaps it if it runs
+// past the deadline.
+fn wait_with_timeout(child: &mut std::process::Child, timeout: std::time::Duration) -> WaitOutcome {
+    let start = std::time::Instant::now();
+    let pid = child.id();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return WaitOutcome::Exited(status),
+            Ok(None) => {
+                if start.elapsed() >= timeout {
+                    kill_process_group(pid);
+                    let _ = child.wait();
+                    return WaitOutcome::TimedOut;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(25));
+            }
+            Err(e) => return WaitOutcome::Error(format!("failed to wait for child: {}", e)),
+        }
+    }
+}
+
+// Caps how much of a stream we keep in memory; read past the cap is still
+// drained (so the pipe doesn't fill and block the child) but discarded.
+const MAX_CAPTURED_OUTPUT_BYTES: usize = 1024 * 1024;
+
+fn read_all(mut pipe: impl std::io::Read) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        match pipe.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => {
+                if buf.len() < MAX_CAPTURED_OUTPUT_BYTES {
+                    let keep = n.min(MAX_CAPTURED_OUTPUT_BYTES - buf.len());
+                    buf.extend_from_slice(&chunk[..keep]);
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    buf
+}
+
+// Strips CSI-style ANSI escape sequences (`ESC [ ... <letter>`) so terminal
+// color codes don't corrupt the JSON shown in the UI.
+fn strip_ansi_codes(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            while let Some(next) = chars.next() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
 impl Agent for SystemAgent {
     fn execute(&self, task: &AgentTask) -> AgentResult {
         let start_time = std::time::Instant::now();
-        
-        let output = std::process::Command::new("sh")
+
+        let mut command = std::process::Command::new("sh");
+        command
             .arg("-c")
             .arg(&task.command)
-            .output();
-            
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                
-                AgentResult {
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+
+        let mut child = match spawn_in_new_process_group(&mut command) {
+            Ok(child) => child,
+            Err(e) => {
+                return AgentResult {
                     task_id: task.id.clone(),
-                    success: output.status.success(),
-                    output: stdout.to_string(),
-                    error: if stderr.is_empty() { None } else { Some(stderr.to_string()) },
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("System command error: {}", e)),
                     execution_time_ms: start_time.elapsed().as_millis() as u64,
-                }
+                    exit_code: None,
+                };
             }
-            Err(e) => AgentResult {
+        };
+
+        let stdout_pipe = child.stdout.take().expect("stdout was piped");
+        let stderr_pipe = child.stderr.take().expect("stderr was piped");
+        let stdout_handle = std::thread::spawn(move || read_all(stdout_pipe));
+        let stderr_handle = std::thread::spawn(move || read_all(stderr_pipe));
+
+        let outcome = match task.timeout_ms {
+            Some(timeout_ms) => wait_with_timeout(&mut child, std::time::Duration::from_millis(timeout_ms)),
+            None => match child.wait() {
+                Ok(status) => WaitOutcome::Exited(status),
+                Err(e) => WaitOutcome::Error(format!("failed to wait for child: {}", e)),
+            },
+        };
+
+        let stdout_bytes = stdout_handle.join().unwrap_or_default();
+        let stderr_bytes = stderr_handle.join().unwrap_or_default();
+        let mut output = String::from_utf8_lossy(&stdout_bytes).into_owned();
+        let mut stderr = String::from_utf8_lossy(&stderr_bytes).into_owned();
+
+        if task.strip_ansi {
+            output = strip_ansi_codes(&output);
+            stderr = strip_ansi_codes(&stderr);
+        }
+
+        let execution_time_ms = start_time.elapsed().as_millis() as u64;
+
+        match outcome {
+            WaitOutcome::Exited(status) => AgentResult {
+                task_id: task.id.clone(),
+                success: status.success(),
+                output,
+                error: if stderr.is_empty() { None } else { Some(stderr) },
+                execution_time_ms,
+                exit_code: status.code(),
+            },
+            WaitOutcome::TimedOut => AgentResult {
                 task_id: task.id.clone(),
                 success: false,
-                output: String::new(),
-                error: Some(format!("System command error: {}", e)),
-                execution_time_ms: start_time.elapsed().as_millis() as u64,
-            }
+                output,
+                error: Some(format!(
+                    "command timed out after {}ms",
+                    task.timeout_ms.unwrap_or(0)
+                )),
+                execution_time_ms,
+                exit_code: None,
+            },
+            WaitOutcome::Error(e) => AgentResult {
+                task_id: task.id.clone(),
+                success: false,
+                output,
+                error: Some(e),
+                execution_time_ms,
+                exit_code: None,
+            },
         }
     }
-    
+
     fn supports(&self, agent_type: &str) -> bool {
         agent_type == "system" || agent_type == "shell" || agent_type == "bash"
     }
@@ -178,15 +495,18 @@ pub fn init_agent_system() -> Result<(), Box<dyn std::error::Error>> {
     
     // Register Python agent
     if let Ok(python_agent) = PythonAgent::new() {
-        registry.insert("python".to_string(), Box::new(python_agent));
+        registry.insert("python".to_string(), Arc::new(python_agent));
     }
-    
+
     // Register WASM agent
-    registry.insert("wasm".to_string(), Box::new(WasmAgent::new()));
-    
+    registry.insert("wasm".to_string(), Arc::new(WasmAgent::new()));
+
     // Register system agent
-    registry.insert("system".to_string(), Box::new(SystemAgent));
-    
+    registry.insert("system".to_string(), Arc::new(SystemAgent));
+
+    // Register platform/capability agent
+    registry.insert("platform".to_string(), Arc::new(platform::PlatformAgent));
+
     Ok(())
 }
 
@@ -201,31 +521,106 @@ pub fn execute_agent_task(task_json: &str) -> String {
                 output: String::new(),
                 error: Some(format!("Failed to parse task JSON: {}", e)),
                 execution_time_ms: 0,
+                exit_code: None,
             };
             return serde_json::to_string(&error_result).unwrap_or_else(|_| "{}".to_string());
         }
     };
     
-    let registry = AGENT_REGISTRY.lock().unwrap();
-    
-    for (_, agent) in registry.iter() {
-        if agent.supports(&task.agent_type) {
-            let result = agent.execute(&task);
-            return serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string());
-        }
+    set_task_state(&task.id, AgentState::Queued);
+
+    let agent = {
+        let registry = AGENT_REGISTRY.lock().unwrap();
+        registry.values().find(|agent| agent.supports(&task.agent_type)).cloned()
+    };
+
+    if let Some(agent) = agent {
+        set_task_state(&task.id, AgentState::Running);
+        let result = agent.execute(&task);
+        set_task_state(
+            &task.id,
+            if result.success { AgentState::Succeeded } else { AgentState::Failed },
+        );
+        return serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string());
     }
-    
+
+    set_task_state(&task.id, AgentState::Failed);
+
     let error_result = AgentResult {
         task_id: task.id,
         success: false,
         output: String::new(),
         error: Some(format!("No agent found for type: {}", task.agent_type)),
         execution_time_ms: 0,
+        exit_code: None,
     };
-    
+
     serde_json::to_string(&error_result).unwrap_or_else(|_| "{}".to_string())
 }
 
+// Execute a batch of tasks, returning a single aggregate result
+pub fn execute_agent_tasks(tasks_json: &str) -> CombinedResult {
+    let tasks: Vec<AgentTask> = match serde_json::from_str(tasks_json) {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            let error_result = AgentResult {
+                task_id: "unknown".to_string(),
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to parse batch JSON: {}", e)),
+                execution_time_ms: 0,
+                exit_code: None,
+            };
+            return CombinedResult::from(vec![error_result]);
+        }
+    };
+
+    let results: Vec<AgentResult> = tasks
+        .iter()
+        .map(|task| {
+            let task_json = serde_json::to_string(task).unwrap_or_else(|_| "{}".to_string());
+            serde_json::from_str(&execute_agent_task(&task_json)).unwrap_or_else(|e| AgentResult {
+                task_id: task.id.clone(),
+                success: false,
+                output: String::new(),
+                error: Some(format!("failed to parse task result: {}", e)),
+                exit_code: None,
+                execution_time_ms: 0,
+            })
+        })
+        .collect();
+
+    CombinedResult::from(results)
+}
+
+// Executes several batches in sequence, folding each into a single running
+// `CombinedResult` via `CombinedResult::merge` so the caller gets one
+// roll-up across all of them instead of one per batch.
+pub fn execute_agent_task_groups(task_groups_json: &str) -> CombinedResult {
+    let groups: Vec<Vec<AgentTask>> = match serde_json::from_str(task_groups_json) {
+        Ok(groups) => groups,
+        Err(e) => {
+            let error_result = AgentResult {
+                task_id: "unknown".to_string(),
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to parse batch groups JSON: {}", e)),
+                execution_time_ms: 0,
+                exit_code: None,
+            };
+            return CombinedResult::from(vec![error_result]);
+        }
+    };
+
+    groups
+        .iter()
+        .map(|tasks| {
+            let tasks_json = serde_json::to_string(tasks).unwrap_or_else(|_| "[]".to_string());
+            execute_agent_tasks(&tasks_json)
+        })
+        .fold(CombinedResult::default(), CombinedResult::merge)
+}
+
 // JNI exports for Android
 #[no_mangle]
 pub extern "C" fn bifrost_hello(input: *const c_char) -> *mut c_char {
@@ -251,6 +646,117 @@ pub extern "C" fn bifrost_execute_task(task_json: *const c_char) -> *mut c_char
     CString::new(result).unwrap().into_raw()
 }
 
+// Submits the task for background execution instead of blocking the caller;
+// collect the eventual result via `bifrost_poll_results`.
+#[no_mangle]
+pub extern "C" fn bifrost_submit_task(task_json: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(task_json) };
+    let task_str = c_str.to_str().unwrap_or("{}");
+
+    let response = match serde_json::from_str::<AgentTask>(task_str) {
+        Ok(task) => {
+            let task_id = task.id.clone();
+            let submitted = executor::submit_task(task);
+            serde_json::json!({ "task_id": task_id, "submitted": submitted })
+        }
+        Err(e) => serde_json::json!({
+            "task_id": "unknown",
+            "submitted": false,
+            "error": format!("Failed to parse task JSON: {}", e),
+        }),
+    };
+
+    CString::new(response.to_string()).unwrap().into_raw()
+}
+
+// Spawns a background thread that loops forever: fetch jobs from
+// `server_url` for `device_uid`, execute them, report results, sleep. The
+// FFI call itself returns immediately.
+#[no_mangle]
+pub extern "C" fn bifrost_run_forever(server_url: *const c_char, device_uid: *const c_char) {
+    let server_url = unsafe { CStr::from_ptr(server_url) }.to_str().unwrap_or("").to_string();
+    let device_uid = unsafe { CStr::from_ptr(device_uid) }.to_str().unwrap_or("").to_string();
+
+    std::thread::spawn(move || {
+        let handler = client::ClientHandler::new(&server_url, &device_uid);
+        handler.run_forever();
+    });
+}
+
+#[no_mangle]
+pub extern "C" fn bifrost_gather_platform() -> *mut c_char {
+    let info = platform::gather();
+    let json = serde_json::to_string(&info).unwrap_or_else(|_| "{}".to_string());
+    CString::new(json).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn bifrost_execute_batch(tasks_json: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(tasks_json) };
+    let tasks_str = c_str.to_str().unwrap_or("[]");
+    let combined = execute_agent_tasks(tasks_str);
+    let json = serde_json::to_string(&combined).unwrap_or_else(|_| "{}".to_string());
+    CString::new(json).unwrap().into_raw()
+}
+
+// Takes a JSON array of task arrays and executes each inner array as a
+// batch, returning one `CombinedResult` merged across all of them.
+#[no_mangle]
+pub extern "C" fn bifrost_execute_batch_groups(task_groups_json: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(task_groups_json) };
+    let task_groups_str = c_str.to_str().unwrap_or("[]");
+    let combined = execute_agent_task_groups(task_groups_str);
+    let json = serde_json::to_string(&combined).unwrap_or_else(|_| "{}".to_string());
+    CString::new(json).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn bifrost_execute_job(job_json: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(job_json) };
+    let job_str = c_str.to_str().unwrap_or("{}");
+
+    let plan: jobs::JobPlan = match serde_json::from_str(job_str) {
+        Ok(plan) => plan,
+        Err(e) => {
+            let error = format!("Failed to parse job JSON: {}", e);
+            return CString::new(serde_json::json!({ "error": error }).to_string())
+                .unwrap()
+                .into_raw();
+        }
+    };
+
+    let results = jobs::execute_job(&plan);
+    let json = serde_json::to_string(&results).unwrap_or_else(|_| "{}".to_string());
+    CString::new(json).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn bifrost_poll_results() -> *mut c_char {
+    let results = executor::pop_completed();
+    let json = serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string());
+    CString::new(json).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn bifrost_task_state(task_id: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(task_id) };
+    let id = c_str.to_str().unwrap_or("");
+
+    let registry = TASK_REGISTRY.lock().unwrap();
+    let json = match registry.get(id) {
+        Some(status) => serde_json::to_string(status).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    };
+    CString::new(json).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn bifrost_list_tasks() -> *mut c_char {
+    let registry = TASK_REGISTRY.lock().unwrap();
+    let json = serde_json::to_string(&*registry).unwrap_or_else(|_| "{}".to_string());
+    CString::new(json).unwrap().into_raw()
+}
+
 #[no_mangle]
 pub extern "C" fn bifrost_run_python(code: *const c_char) -> *mut c_char {
     let c_str = unsafe { CStr::from_ptr(code) };
@@ -262,6 +768,8 @@ pub extern "C" fn bifrost_run_python(code: *const c_char) -> *mut c_char {
         command: code_str.to_string(),
         args: vec![],
         environment: HashMap::new(),
+        timeout_ms: None,
+        strip_ansi: false,
     };
     
     let result = execute_agent_task(&serde_json::to_string(&task).unwrap());
@@ -276,3 +784,101 @@ pub extern "C" fn bifrost_free_string(ptr: *mut c_char) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_ansi_codes_removes_color_sequences() {
+        let input = "\u{1b}[31mred\u{1b}[0m text";
+        assert_eq!(strip_ansi_codes(input), "red text");
+    }
+
+    #[test]
+    fn strip_ansi_codes_is_noop_on_plain_text() {
+        assert_eq!(strip_ansi_codes("plain output\n"), "plain output\n");
+    }
+
+    fn system_task(command: &str, timeout_ms: Option<u64>) -> AgentTask {
+        AgentTask {
+            id: "system-test".to_string(),
+            agent_type: "system".to_string(),
+            command: command.to_string(),
+            args: vec![],
+            environment: HashMap::new(),
+            timeout_ms,
+            strip_ansi: false,
+        }
+    }
+
+    #[test]
+    fn system_agent_propagates_nonzero_exit_code() {
+        let result = SystemAgent.execute(&system_task("exit 7", None));
+        assert!(!result.success);
+        assert_eq!(result.exit_code, Some(7));
+    }
+
+    #[test]
+    fn system_agent_kills_process_group_on_timeout() {
+        // The backgrounded sleep holds the output pipes open; only killing the
+        // whole group lets the read threads finish well before 5s.
+        let result = SystemAgent.execute(&system_task("sleep 5 & sleep 5", Some(100)));
+        assert!(!result.success);
+        assert_eq!(result.exit_code, None);
+        assert!(result.execution_time_ms < 5000);
+        assert!(result.error.unwrap_or_default().contains("timed out"));
+    }
+
+    fn agent_result(success: bool) -> AgentResult {
+        AgentResult {
+            task_id: "t".to_string(),
+            success,
+            output: String::new(),
+            error: None,
+            execution_time_ms: 10,
+            exit_code: None,
+        }
+    }
+
+    #[test]
+    fn combined_result_merge_accumulates_across_batches() {
+        let first = CombinedResult::from(vec![agent_result(true)]);
+        let second = CombinedResult::from(vec![agent_result(true), agent_result(false)]);
+
+        let merged = first.merge(second);
+
+        assert_eq!(merged.total, 3);
+        assert_eq!(merged.succeeded, 2);
+        assert_eq!(merged.failed, 1);
+        assert_eq!(merged.total_time_ms, 30);
+        assert!(!merged.success);
+    }
+
+    fn wasm_task(wat: &str, args: Vec<String>) -> AgentTask {
+        AgentTask {
+            id: "wasm-test".to_string(),
+            agent_type: "wasm".to_string(),
+            command: format!("base64:{}", base64::encode(wat)),
+            args,
+            environment: HashMap::new(),
+            timeout_ms: None,
+            strip_ansi: false,
+        }
+    }
+
+    #[test]
+    fn wasm_agent_runs_trivial_module() {
+        let wat = r#"(module (func (export "_start")))"#;
+        let result = WasmAgent::new().execute(&wasm_task(wat, vec![]));
+        assert!(result.success, "expected success, got error: {:?}", result.error);
+    }
+
+    #[test]
+    fn wasm_agent_reports_trap_as_failure() {
+        let wat = r#"(module (func (export "_start") unreachable))"#;
+        let result = WasmAgent::new().execute(&wasm_task(wat, vec![]));
+        assert!(!result.success);
+        assert!(result.error.unwrap_or_default().contains("WASM trap"));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Agent, AgentResult, AgentTask, AGENT_REGISTRY};
+use serde::Serialize;
+use std::process::Command;
+
+// Device capabilities a coordinator can use to decide which jobs this
+// device is able to run (e.g. don't dispatch WASM/Python jobs to a device
+// missing those agents).
+#[derive(Debug, Serialize)]
+pub struct PlatformInfo {
+    pub os: String,
+    pub kernel: String,
+    pub arch: String,
+    pub hostname: String,
+    pub cpu_count: usize,
+    pub available_memory_kb: Option<u64>,
+    pub python_version: Option<String>,
+    pub has_sh: bool,
+    pub available_agent_types: Vec<String>,
+}
+
+pub fn gather() -> PlatformInfo {
+    PlatformInfo {
+        os: std::env::consts::OS.to_string(),
+        kernel: command_output("uname", &["-r"]),
+        arch: std::env::consts::ARCH.to_string(),
+        hostname: command_output("hostname", &[]),
+        cpu_count: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        available_memory_kb: available_memory_kb(),
+        python_version: python_version(),
+        has_sh: on_path("sh"),
+        available_agent_types: registered_agent_types(),
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> String {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// Reads `MemAvailable` from /proc/meminfo (kernel's estimate of memory
+// available for new workloads without swapping); `None` off Linux or if the
+// file can't be parsed.
+fn available_memory_kb() -> Option<u64> {
+    let contents = std::fs::read_to_string("/proc/meminfo").ok()?;
+    contents.lines().find_map(|line| {
+        let rest = line.strip_prefix("MemAvailable:")?;
+        rest.trim().split_whitespace().next()?.parse().ok()
+    })
+}
+
+fn python_version() -> Option<String> {
+    Command::new("python3").arg("--version").output().ok().and_then(|output| {
+        let bytes = if !output.stdout.is_empty() { &output.stdout } else { &output.stderr };
+        let version = String::from_utf8_lossy(bytes).trim().to_string();
+        if version.is_empty() { None } else { Some(version) }
+    })
+}
+
+fn on_path(program: &str) -> bool {
+    Command::new("which").arg(program).output().map(|output| output.status.success()).unwrap_or(false)
+}
+
+fn registered_agent_types() -> Vec<String> {
+    AGENT_REGISTRY.lock().unwrap().keys().cloned().collect()
+}
+
+// Reports this device's capabilities instead of running a task; registered
+// under agent_type "platform" so a coordinator can call it once at
+// enrollment.
+pub struct PlatformAgent;
+
+impl Agent for PlatformAgent {
+    fn execute(&self, task: &AgentTask) -> AgentResult {
+        let start_time = std::time::Instant::now();
+        let info = gather();
+
+        AgentResult {
+            task_id: task.id.clone(),
+            success: true,
+            output: serde_json::to_string(&info).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+            exit_code: None,
+        }
+    }
+
+    fn supports(&self, agent_type: &str) -> bool {
+        agent_type == "platform"
+    }
+}